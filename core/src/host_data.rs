@@ -1,6 +1,6 @@
 use std::{
     cell::RefCell,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     future::Future,
     sync::{
         atomic::{AtomicU32, Ordering},
@@ -12,7 +12,26 @@ use std::{
 use anymap::AnyMap;
 use tokio::task::JoinHandle;
 
-use crate::{Interval, IntervalId, MacroTask, TaskId};
+use crate::{Interval, IntervalId, MacroTask, ModuleMap, ResourceTable, TaskId};
+
+/// Identifies a supervision group: a set of related async tasks (and nested
+/// child groups) that can be cancelled together. Borrowed from the
+/// supervision-tree model so an `AbortController` or a module unload can tear
+/// down every timer and op it spawned in one call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GroupId(u32);
+
+impl GroupId {
+    /// Construct a [`GroupId`] from its accumulative index.
+    pub fn from_index(index: u32) -> Self {
+        Self(index)
+    }
+
+    /// The underlying index of this group.
+    pub fn index(&self) -> u32 {
+        self.0
+    }
+}
 
 /// Data created and used by the Runtime.
 pub struct HostData {
@@ -30,6 +49,19 @@ pub struct HostData {
     pub tasks: RefCell<HashMap<TaskId, JoinHandle<()>>>,
     /// Counter of accumulative created async tasks.  Used for ID generation.
     pub task_count: Arc<AtomicU32>,
+    /// Memoizes already-loaded ES modules by canonical specifier so circular and
+    /// diamond imports are only evaluated once.
+    pub module_map: ModuleMap,
+    /// Membership of each supervision group: the tasks spawned into it.
+    pub groups: RefCell<HashMap<GroupId, HashSet<TaskId>>>,
+    /// Supervision tree edges: the child groups of each group, aborted
+    /// cascadingly when the parent is aborted.
+    pub group_children: RefCell<HashMap<GroupId, HashSet<GroupId>>>,
+    /// Counter of accumulative created groups. Used for ID generation.
+    pub group_count: Arc<AtomicU32>,
+    /// Table of open resource handles (files, etc.) addressed by
+    /// [`ResourceId`](crate::ResourceId) rather than re-opened by path per op.
+    pub resource_table: ResourceTable,
 }
 
 impl HostData {
@@ -44,6 +76,11 @@ impl HostData {
                 intervals: RefCell::default(),
                 tasks: RefCell::default(),
                 task_count: Arc::default(),
+                module_map: ModuleMap::new(),
+                groups: RefCell::default(),
+                group_children: RefCell::default(),
+                group_count: Arc::default(),
+                resource_table: ResourceTable::new(),
             },
             rx,
         )
@@ -72,13 +109,92 @@ impl HostData {
         let task_id = TaskId::from_index(self.task_count.fetch_add(1, Ordering::Relaxed));
         self.tasks.borrow_mut().insert(task_id, task_handle);
 
+        tracing::trace!(
+            task_id = ?task_id,
+            pending = self.macro_task_count.load(Ordering::Relaxed),
+            "spawned macro task"
+        );
+
         task_id
     }
 
     /// Abort a MacroTask execution given it's [TaskId].
+    ///
+    /// Only decrements `macro_task_count` when the task was still running: a
+    /// task that has already completed decremented the counter itself in its
+    /// spawn wrapper (but leaves its handle in the registry), so decrementing
+    /// again here would underflow the `AtomicU32` and wedge the event loop.
     pub fn abort_macro_task(&self, task_id: TaskId) {
-        let task = self.tasks.borrow_mut().remove(&task_id).unwrap();
+        let Some(task) = self.tasks.borrow_mut().remove(&task_id) else {
+            return;
+        };
+        let was_running = !task.is_finished();
         task.abort();
-        self.macro_task_count.fetch_sub(1, Ordering::Relaxed);
+        if was_running {
+            self.macro_task_count.fetch_sub(1, Ordering::Relaxed);
+        }
+
+        tracing::trace!(
+            task_id = ?task_id,
+            pending = self.macro_task_count.load(Ordering::Relaxed),
+            "aborted macro task"
+        );
+    }
+
+    /// Create a new, empty supervision [`GroupId`].
+    pub fn new_group(&self) -> GroupId {
+        let group_id = GroupId::from_index(self.group_count.fetch_add(1, Ordering::Relaxed));
+        self.groups.borrow_mut().insert(group_id, HashSet::new());
+        group_id
+    }
+
+    /// Create a child group nested under `parent`. Aborting `parent` cascades
+    /// to the returned group.
+    pub fn new_child_group(&self, parent: GroupId) -> GroupId {
+        let child = self.new_group();
+        self.group_children
+            .borrow_mut()
+            .entry(parent)
+            .or_default()
+            .insert(child);
+        child
+    }
+
+    /// Spawn an async task like [`HostData::spawn_macro_task`], attaching it to
+    /// `group` so it can be cancelled together with the group's other members.
+    pub fn spawn_macro_task_in_group<F>(&self, future: F, group: GroupId) -> TaskId
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let task_id = self.spawn_macro_task(future);
+        self.groups
+            .borrow_mut()
+            .entry(group)
+            .or_default()
+            .insert(task_id);
+        task_id
+    }
+
+    /// Abort every task in `group` and, cascadingly, every task in its child
+    /// groups. Each member is routed through [`HostData::abort_macro_task`],
+    /// which is a no-op for an unknown handle and only decrements
+    /// `macro_task_count` for tasks that were still running, so the counter
+    /// stays consistent even when some members have already completed.
+    pub fn abort_group(&self, group: GroupId) {
+        // Abort child groups first so the tree is torn down leaf-upward.
+        let children = self
+            .group_children
+            .borrow_mut()
+            .remove(&group)
+            .unwrap_or_default();
+        for child in children {
+            self.abort_group(child);
+        }
+
+        let members = self.groups.borrow_mut().remove(&group).unwrap_or_default();
+        for task_id in members {
+            self.abort_macro_task(task_id);
+        }
     }
 }
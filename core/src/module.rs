@@ -0,0 +1,197 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    future::Future,
+    path::{Path, PathBuf},
+    pin::Pin,
+};
+
+/// A fully resolved module specifier.
+///
+/// Resolution always yields either a canonical filesystem path or, for a
+/// specifier that carries a `scheme://` prefix, an absolute URL. Paths are
+/// canonicalized so that the same file reached through different relative
+/// paths hashes equal and is only ever evaluated once by the [`ModuleMap`];
+/// URLs are kept verbatim and are not normalized.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ModuleSpecifier {
+    /// A canonicalized path on the local filesystem.
+    Path(PathBuf),
+    /// An absolute URL specifier (e.g. `file://`, `https://`).
+    Url(String),
+}
+
+impl ModuleSpecifier {
+    /// The string form used as the stable key inside the [`ModuleMap`].
+    pub fn as_key(&self) -> String {
+        match self {
+            ModuleSpecifier::Path(path) => path.to_string_lossy().into_owned(),
+            ModuleSpecifier::Url(url) => url.clone(),
+        }
+    }
+}
+
+/// The source text of a module together with the specifier it resolved from.
+pub struct ModuleSource {
+    pub specifier: ModuleSpecifier,
+    pub code: String,
+}
+
+/// Error raised while resolving or loading a module. These are surfaced to the
+/// running script as JS exceptions rather than aborting the process.
+#[derive(Debug)]
+pub enum ModuleError {
+    /// The specifier could not be resolved against the referrer.
+    Resolution(String),
+    /// The resolved module could not be read.
+    Io(std::io::Error),
+}
+
+impl std::fmt::Display for ModuleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModuleError::Resolution(msg) => write!(f, "Cannot resolve module: {msg}"),
+            ModuleError::Io(err) => write!(f, "Cannot load module: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ModuleError {}
+
+impl From<std::io::Error> for ModuleError {
+    fn from(err: std::io::Error) -> Self {
+        ModuleError::Io(err)
+    }
+}
+
+/// A future yielding a loaded [`ModuleSource`], matching Deno's `ModuleLoader`
+/// load contract. The runtime's static-import hook drives it to completion with
+/// a blocking executor; dynamic `import()` expressions are not handled.
+pub type ModuleSourceFuture =
+    Pin<Box<dyn Future<Output = Result<ModuleSource, ModuleError>> + 'static>>;
+
+/// Pluggable strategy for turning specifiers into runnable module sources.
+///
+/// Implementors own specifier resolution and IO; the runtime owns memoization
+/// (via [`ModuleMap`]) and evaluation. This mirrors Deno's split between the
+/// embedder-supplied `ModuleLoader` and the core `ModuleMap`.
+pub trait ModuleLoader {
+    /// Resolve `specifier` relative to `referrer` into a canonical specifier.
+    fn resolve(
+        &self,
+        specifier: &str,
+        referrer: &ModuleSpecifier,
+    ) -> Result<ModuleSpecifier, ModuleError>;
+
+    /// Load the source for an already resolved `specifier`.
+    fn load(&self, specifier: ModuleSpecifier) -> ModuleSourceFuture;
+}
+
+/// The default loader: resolves relative/absolute filesystem paths and reads
+/// them synchronously inside the returned future, so the file is read on the
+/// future's first poll.
+#[derive(Default)]
+pub struct FsModuleLoader;
+
+impl ModuleLoader for FsModuleLoader {
+    fn resolve(
+        &self,
+        specifier: &str,
+        referrer: &ModuleSpecifier,
+    ) -> Result<ModuleSpecifier, ModuleError> {
+        // Optional URL specifiers are passed through untouched.
+        if let Some(scheme) = specifier.split_once("://") {
+            let _ = scheme;
+            return Ok(ModuleSpecifier::Url(specifier.to_string()));
+        }
+
+        let base = match referrer {
+            ModuleSpecifier::Path(path) => path
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| PathBuf::from(".")),
+            ModuleSpecifier::Url(url) => {
+                return Err(ModuleError::Resolution(format!(
+                    "relative import {specifier:?} from URL referrer {url:?}"
+                )))
+            }
+        };
+
+        let candidate = base.join(specifier);
+        let canonical = std::fs::canonicalize(&candidate).map_err(|err| {
+            ModuleError::Resolution(format!("{}: {err}", candidate.display()))
+        })?;
+        Ok(ModuleSpecifier::Path(canonical))
+    }
+
+    fn load(&self, specifier: ModuleSpecifier) -> ModuleSourceFuture {
+        Box::pin(async move {
+            let code = match &specifier {
+                ModuleSpecifier::Path(path) => std::fs::read_to_string(path)?,
+                ModuleSpecifier::Url(url) => {
+                    return Err(ModuleError::Resolution(format!(
+                        "no transport registered for URL specifier {url:?}"
+                    )))
+                }
+            };
+            Ok(ModuleSource { specifier, code })
+        })
+    }
+}
+
+/// Memoizes loaded modules by their canonical specifier so that circular and
+/// diamond import graphs evaluate each module exactly once.
+#[derive(Default)]
+pub struct ModuleMap {
+    loaded: RefCell<HashMap<String, ModuleStatus>>,
+}
+
+/// Tracks whether a module is mid-evaluation (to break cycles) or finished.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ModuleStatus {
+    Evaluating,
+    Evaluated,
+}
+
+impl ModuleMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if the module has already begun (or finished) evaluating,
+    /// marking it `Evaluating` otherwise. The first caller to observe `false`
+    /// owns evaluation; later callers short-circuit, which is what keeps cyclic
+    /// imports from re-executing.
+    pub fn begin(&self, specifier: &ModuleSpecifier) -> bool {
+        let key = specifier.as_key();
+        let mut loaded = self.loaded.borrow_mut();
+        if loaded.contains_key(&key) {
+            true
+        } else {
+            loaded.insert(key, ModuleStatus::Evaluating);
+            false
+        }
+    }
+
+    /// Mark a module as fully evaluated.
+    pub fn finish(&self, specifier: &ModuleSpecifier) {
+        self.loaded
+            .borrow_mut()
+            .insert(specifier.as_key(), ModuleStatus::Evaluated);
+    }
+
+    /// Drop a module that failed to load or evaluate so a later import of the
+    /// same specifier retries from scratch instead of observing a poisoned
+    /// `Evaluating` entry and silently skipping the load.
+    pub fn abort(&self, specifier: &ModuleSpecifier) {
+        self.loaded.borrow_mut().remove(&specifier.as_key());
+    }
+
+    /// Whether `specifier` has finished evaluating.
+    pub fn is_evaluated(&self, specifier: &ModuleSpecifier) -> bool {
+        matches!(
+            self.loaded.borrow().get(&specifier.as_key()),
+            Some(ModuleStatus::Evaluated)
+        )
+    }
+}
@@ -0,0 +1,69 @@
+/// Capability flags describing what host resources a script may touch.
+///
+/// A single [`Permissions`] value is stored in [`HostData::storage`](crate::HostData)
+/// and consulted by ops before they perform a privileged action. Denied access
+/// is surfaced to the script as a JS exception rather than a silent no-op.
+#[derive(Debug, Clone, Copy)]
+pub struct Permissions {
+    /// Allow reading from the filesystem.
+    pub read: bool,
+    /// Allow writing to the filesystem.
+    pub write: bool,
+    /// Allow full-resolution high-resolution timers. When denied, timer
+    /// readings are clamped to a coarse grid to mitigate timing side channels.
+    pub hrtime: bool,
+}
+
+impl Permissions {
+    /// All capabilities denied — the safe default for untrusted code.
+    pub const fn deny_all() -> Self {
+        Self {
+            read: false,
+            write: false,
+            hrtime: false,
+        }
+    }
+
+    /// Return `Ok(())` if `read` is granted, otherwise a denial message suitable
+    /// for throwing as a JS error.
+    pub fn check_read(&self) -> Result<(), PermissionDenied> {
+        self.check(self.read, "read")
+    }
+
+    /// Return `Ok(())` if `write` is granted, otherwise a denial message.
+    pub fn check_write(&self) -> Result<(), PermissionDenied> {
+        self.check(self.write, "write")
+    }
+
+    fn check(&self, granted: bool, name: &'static str) -> Result<(), PermissionDenied> {
+        if granted {
+            Ok(())
+        } else {
+            Err(PermissionDenied { name })
+        }
+    }
+}
+
+impl Default for Permissions {
+    fn default() -> Self {
+        Self::deny_all()
+    }
+}
+
+/// A denied permission check, carrying the capability name for the error text.
+#[derive(Debug)]
+pub struct PermissionDenied {
+    name: &'static str,
+}
+
+impl std::fmt::Display for PermissionDenied {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Requires {name} access, run again with --allow-{name}",
+            name = self.name
+        )
+    }
+}
+
+impl std::error::Error for PermissionDenied {}
@@ -0,0 +1,104 @@
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::BTreeMap,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+};
+
+/// Numeric handle identifying an open resource in the [`ResourceTable`].
+///
+/// Handed to scripts in place of re-opening paths by string on every op, so a
+/// single `open` call can back a sequence of streaming `read`/`write`/`seek`
+/// operations followed by `close`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ResourceId(u32);
+
+impl ResourceId {
+    /// Reconstruct a handle from its numeric index, e.g. the value an op
+    /// receives back from script after `open`.
+    pub fn from_index(index: u32) -> Self {
+        Self(index)
+    }
+
+    /// The underlying index of this resource handle.
+    pub fn index(&self) -> u32 {
+        self.0
+    }
+}
+
+/// A future returned by [`Resource::close`].
+pub type CloseFuture = Pin<Box<dyn Future<Output = ()> + 'static>>;
+
+/// An open, handle-addressable resource (a file, socket, …).
+///
+/// Mirrors deno_core's `Resource`: the runtime owns the table and lifetime, and
+/// implementors expose a `close` hook so the underlying handle is dropped
+/// deterministically rather than at an arbitrary GC point.
+pub trait Resource {
+    /// Human-readable name used in diagnostics (e.g. `"fsFile"`).
+    fn name(&self) -> &str;
+
+    /// Downcast hook so ops can recover the concrete resource type behind the
+    /// `dyn Resource` stored in the table. Implementors return `self`.
+    fn as_any(&self) -> &dyn Any;
+
+    /// Release the underlying handle. The default drops on return; override for
+    /// resources that must flush or await cleanup.
+    fn close(self: Rc<Self>) -> CloseFuture {
+        Box::pin(async {})
+    }
+}
+
+/// Maps incrementing [`ResourceId`]s to reference-counted [`Resource`] trait objects.
+///
+/// Kept in [`HostData`](crate::HostData) so ops can look a handle up instead of
+/// threading owned file objects through the VM. Uses a `BTreeMap` so iteration
+/// at shutdown visits resources in a stable, oldest-first order.
+#[derive(Default)]
+pub struct ResourceTable {
+    index: RefCell<BTreeMap<ResourceId, Rc<dyn Resource>>>,
+    next_id: RefCell<u32>,
+}
+
+impl ResourceTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert `resource`, returning its freshly allocated [`ResourceId`].
+    pub fn add<R: Resource + 'static>(&self, resource: R) -> ResourceId {
+        let mut next = self.next_id.borrow_mut();
+        let id = ResourceId(*next);
+        *next += 1;
+        self.index.borrow_mut().insert(id, Rc::new(resource));
+        id
+    }
+
+    /// Look up a resource handle, cloning the `Rc` so the caller can operate on
+    /// it without holding the table borrow.
+    pub fn get(&self, id: ResourceId) -> Option<Rc<dyn Resource>> {
+        self.index.borrow().get(&id).cloned()
+    }
+
+    /// Remove `id` from the table and run its [`Resource::close`] hook, freeing
+    /// the slot. Returns the close future; `None` if the handle was unknown.
+    pub fn close(&self, id: ResourceId) -> Option<CloseFuture> {
+        let resource = self.index.borrow_mut().remove(&id)?;
+        Some(resource.close())
+    }
+
+    /// Close every outstanding resource. Called at runtime shutdown so no file
+    /// handle is leaked past the lifetime of the runtime.
+    pub fn close_all(&self) {
+        let drained: Vec<_> = std::mem::take(&mut *self.index.borrow_mut())
+            .into_values()
+            .collect();
+        for resource in drained {
+            // Drive each close hook to completion on the current thread; these
+            // are expected to be cheap handle drops at shutdown.
+            futures::executor::block_on(resource.close());
+        }
+    }
+}
@@ -9,8 +9,8 @@ use std::{
 use nova_vm::ecmascript::{
     builtins::promise_objects::promise_abstract_operations::promise_capability_records::PromiseCapability,
     execution::{
-        agent::{HostHooks, Job, Options},
-        initialize_host_defined_realm, Agent, JsResult, Realm,
+        agent::{ExceptionType, HostHooks, Job, Options},
+        initialize_host_defined_realm, Agent, JsError, JsResult, Realm,
     },
     scripts_and_modules::{
         script::{parse_script, script_evaluation},
@@ -23,13 +23,21 @@ use oxc_ast::ast;
 
 use crate::{
     exit_with_parse_errors, initialize_recommended_builtins, initialize_recommended_extensions,
-    HostData, MacroTask,
+    FsModuleLoader, HostData, MacroTask, ModuleLoader, ModuleSpecifier, Permissions,
+    RuntimeActivity,
 };
 
 pub struct RuntimeHostHooks {
     allocator: Allocator,
     promise_job_queue: RefCell<VecDeque<Job>>,
     host_data: HostData,
+    module_loader: Box<dyn ModuleLoader>,
+    /// The exception from the most recent failed static import. The VM's
+    /// `import_module` hook returns `()` with no error channel, so the error is
+    /// stashed here and re-surfaced by [`Runtime::run`] once evaluation of the
+    /// importing script unwinds — a failing import is propagated, never a
+    /// silent success and never a process abort.
+    pending_import_error: RefCell<Option<JsError>>,
 }
 
 impl std::fmt::Debug for RuntimeHostHooks {
@@ -40,13 +48,121 @@ impl std::fmt::Debug for RuntimeHostHooks {
 
 impl RuntimeHostHooks {
     pub fn new(host_data: HostData, allocator: Allocator) -> Self {
+        Self::with_module_loader(host_data, allocator, Box::new(FsModuleLoader))
+    }
+
+    /// Create the host hooks with a custom [`ModuleLoader`]. Embedders use this
+    /// to override specifier resolution or supply a network transport.
+    pub fn with_module_loader(
+        host_data: HostData,
+        allocator: Allocator,
+        module_loader: Box<dyn ModuleLoader>,
+    ) -> Self {
         Self {
             promise_job_queue: RefCell::default(),
             host_data,
             allocator,
+            module_loader,
+            pending_import_error: RefCell::default(),
+        }
+    }
+
+    /// Take the stashed static-import error, if any. Called by [`Runtime::run`]
+    /// after each top-level script evaluation to propagate a failed import.
+    pub fn take_import_error(&self) -> Option<JsError> {
+        self.pending_import_error.borrow_mut().take()
+    }
+
+    /// Resolve, load and evaluate `specifier` relative to `referrer`, reusing an
+    /// already-evaluated module when the [`ModuleMap`](crate::ModuleMap) has one.
+    /// IO and parse failures are returned as JS exceptions rather than aborting.
+    fn evaluate_module(
+        &self,
+        agent: &mut Agent,
+        specifier: &str,
+        referrer: &ModuleSpecifier,
+    ) -> JsResult<()> {
+        let realm_id = agent.current_realm_id();
+
+        let resolved = match self.module_loader.resolve(specifier, referrer) {
+            Ok(resolved) => resolved,
+            Err(err) => {
+                return Err(
+                    agent.throw_exception(ExceptionType::Error, err.to_string())
+                )
+            }
+        };
+
+        // A module that has begun evaluating (cycle) or finished (diamond) is
+        // never re-executed.
+        if self.host_data.module_map.begin(&resolved) {
+            return Ok(());
+        }
+
+        let source = match futures::executor::block_on(self.module_loader.load(resolved.clone())) {
+            Ok(source) => source,
+            Err(err) => {
+                // Drop the `Evaluating` marker so a later retry isn't poisoned
+                // into a silent no-op by this transient failure.
+                self.host_data.module_map.abort(&resolved);
+                return Err(agent.throw_exception(ExceptionType::Error, err.to_string()));
+            }
+        };
+
+        let host_path = match &resolved {
+            ModuleSpecifier::Path(path) => path.clone(),
+            ModuleSpecifier::Url(url) => PathBuf::from(url),
+        };
+        let script = match parse_script(
+            &self.allocator,
+            source.code.into(),
+            realm_id,
+            false,
+            Some(Box::leak(Box::new(host_path))),
+        ) {
+            Ok(script) => script,
+            Err((_file, errors)) => {
+                // Surface the parse failure as a JS exception instead of
+                // aborting the whole process.
+                self.host_data.module_map.abort(&resolved);
+                let message = format!("Failed to parse module {specifier:?}: {errors:?}");
+                return Err(agent.throw_exception(ExceptionType::SyntaxError, message));
+            }
+        };
+        match script_evaluation(agent, script) {
+            Ok(_) => {
+                self.host_data.module_map.finish(&resolved);
+                Ok(())
+            }
+            Err(err) => {
+                self.host_data.module_map.abort(&resolved);
+                Err(err)
+            }
         }
     }
 
+    /// The specifier of the script or module currently on top of the execution
+    /// stack, used as the base for relative import resolution. Falls back to the
+    /// current working directory when no host path was recorded.
+    fn referrer_specifier(&self, agent: &Agent) -> ModuleSpecifier {
+        let referrer_path = agent
+            .running_execution_context()
+            .script_or_module
+            .and_then(|script_or_module| match script_or_module {
+                ScriptOrModule::Script(script_id) => {
+                    agent[script_id].host_defined.as_ref().and_then(|host| {
+                        host.downcast_ref::<PathBuf>().map(PathBuf::to_path_buf)
+                    })
+                }
+                // Module referrers carry no host path here and fall back to
+                // the working directory like an unrecorded script path.
+                _ => None,
+            })
+            .unwrap_or_else(|| PathBuf::from("."));
+        ModuleSpecifier::Path(referrer_path)
+    }
+
+
     pub fn pop_promise_job(&self) -> Option<Job> {
         self.promise_job_queue.borrow_mut().pop_front()
     }
@@ -65,41 +181,20 @@ impl HostHooks for RuntimeHostHooks {
         &self.host_data
     }
 
-    // TODO: Implement a transport abstraction.
     fn import_module(&self, import: &ast::ImportDeclaration<'_>, agent: &mut Agent) {
-        let realm_id = agent.current_realm_id();
-
-        let script_or_module = agent.running_execution_context().script_or_module.unwrap();
-        let script_id = match script_or_module {
-            ScriptOrModule::Script(script_id) => script_id,
-            _ => todo!(),
-        };
-        let script = &agent[script_id];
-
-        let current_host_path = script.host_defined.as_ref().unwrap();
-        let mut current_host_path = current_host_path
-            .downcast_ref::<PathBuf>()
-            .unwrap()
-            .to_path_buf();
-        current_host_path.pop(); // Use the parent folder
-        let current_host_path = std::fs::canonicalize(&current_host_path).unwrap();
-
+        // Derive the referrer specifier from whichever script or module is
+        // currently executing.
+        let referrer = self.referrer_specifier(agent);
         let import_path = import.source.value.as_str();
-        let host_path = current_host_path.join(import_path);
-        let host_path = std::fs::canonicalize(host_path).unwrap();
-
-        let file = std::fs::read_to_string(&host_path).unwrap();
-        let script = match parse_script(
-            &self.allocator,
-            file.into(),
-            realm_id,
-            false,
-            Some(Box::leak(Box::new(host_path))),
-        ) {
-            Ok(script) => script,
-            Err((file, errors)) => exit_with_parse_errors(errors, import_path, &file),
-        };
-        script_evaluation(agent, script).unwrap();
+        // The hook has no error channel (returns `()`), so stash the first
+        // failure for `Runtime::run` to re-surface instead of aborting or
+        // swallowing it.
+        if let Err(err) = self.evaluate_module(agent, import_path, &referrer) {
+            let mut slot = self.pending_import_error.borrow_mut();
+            if slot.is_none() {
+                *slot = Some(err);
+            }
+        }
     }
 }
 
@@ -107,6 +202,14 @@ pub struct RuntimeConfig {
     pub no_strict: bool,
     pub paths: Vec<String>,
     pub verbose: bool,
+    /// Enable the live runtime-activity console (tracing subscriber).
+    pub console: bool,
+    /// Allow filesystem reads.
+    pub allow_read: bool,
+    /// Allow filesystem writes.
+    pub allow_write: bool,
+    /// Allow full-resolution high-resolution timers.
+    pub allow_hrtime: bool,
 }
 
 pub struct Runtime {
@@ -119,8 +222,20 @@ pub struct Runtime {
 impl Runtime {
     /// Create a new [Runtime] given a [RuntimeConfig]. Use [Runtime::run] to run it.
     pub fn new(config: RuntimeConfig) -> Self {
+        // Install the runtime console before anything is spawned so no task or
+        // interval span is missed.
+        if config.console {
+            crate::install_console_subscriber();
+        }
+
         let allocator = Allocator::default();
         let (host_data, macro_task_rx) = HostData::new();
+        // Seed the permission set from the config before any op can run.
+        host_data.storage.borrow_mut().insert(Permissions {
+            read: config.allow_read,
+            write: config.allow_write,
+            hrtime: config.allow_hrtime,
+        });
         let host_hooks = RuntimeHostHooks::new(host_data, allocator);
 
         let host_hooks: &RuntimeHostHooks = &*Box::leak(Box::new(host_hooks));
@@ -154,7 +269,7 @@ impl Runtime {
     pub fn run(&mut self) -> JsResult<Value> {
         let realm = self.agent.current_realm_id();
 
-        // LOad the builtins js sources
+        // Load the builtin js sources.
         initialize_recommended_builtins(
             &self.host_hooks.allocator,
             &mut self.agent,
@@ -180,30 +295,84 @@ impl Runtime {
             final_result = match script_evaluation(&mut self.agent, script) {
                 Ok(v) => v,
                 err => return err,
+            };
+
+            // A static `import` that failed stashed its exception (the hook
+            // can't return it); surface it now instead of continuing as if the
+            // import had succeeded.
+            if let Some(err) = self.host_hooks.take_import_error() {
+                return Err(err);
             }
         }
 
+        // Event loop: run promise jobs to quiescence, resolve every macro task
+        // that has already been delivered in one batch (rather than one per
+        // `recv`), then park until the next task arrives or a timer fires. This
+        // removes the head-of-line blocking of the old one-at-a-time loop; the
+        // async ops themselves run concurrently on the Tokio runtime.
         loop {
+            // (1) Run all pending promise jobs to quiescence.
             while let Some(job) = self.host_hooks.pop_promise_job() {
                 job.run(&mut self.agent)?;
             }
 
-            // If both the microtasks and macrotasks queues are empty we can end the event loop
+            // Exit only when both the microtask and macro-task queues are empty.
             if !self.host_hooks.any_pending_macro_tasks() {
                 break;
             }
 
-            self.handle_macro_task();
+            // Surface live activity counts each tick when the runtime console is
+            // enabled, so hung loops and leaked timers are visible.
+            if self.config.console {
+                let activity = RuntimeActivity::sample(&self.host_hooks.host_data);
+                tracing::info!(
+                    target: "runtime::console",
+                    pending_macro_tasks = activity.pending_macro_tasks,
+                    active_intervals = activity.active_intervals,
+                    active_tasks = activity.active_tasks,
+                    "runtime activity"
+                );
+            }
+
+            // (2) Resolve every macro task that has already been delivered in a
+            // single batch, so a burst of completions (overlapping timers, fs
+            // and future network ops all reported ready) is cleared in one tick
+            // instead of one per `recv`. The ops themselves run concurrently on
+            // the Tokio runtime; this loop only resolves their promise
+            // capabilities, which must happen on the single-threaded agent.
+            let mut handled_any = false;
+            while let Ok(macro_task) = self.macro_task_rx.try_recv() {
+                self.handle_macro_task(macro_task);
+                handled_any = true;
+            }
+
+            // (3) If nothing was ready, park on the waker until the next macro
+            // task arrives (a timer firing or an async op completing).
+            if !handled_any {
+                if let Ok(macro_task) = self.macro_task_rx.recv() {
+                    self.handle_macro_task(macro_task);
+                }
+            }
         }
 
+        // Shutdown: close every outstanding resource so no file handle outlives
+        // the runtime.
+        self.host_hooks.host_data.resource_table.close_all();
+
         Ok(final_result)
     }
 
-    // Listen for pending macro tasks and resolve one by one
-    pub fn handle_macro_task(&mut self) {
+    /// Resolve a single ready macro task. Called in a batch by the event loop
+    /// for every task drained in one poll pass.
+    pub fn handle_macro_task(&mut self, macro_task: MacroTask) {
+        let _span = tracing::trace_span!(
+            "handle_macro_task",
+            pending = self.host_hooks.host_data.macro_task_count.load(Ordering::Relaxed)
+        )
+        .entered();
         #[allow(clippy::single_match)]
-        match self.macro_task_rx.recv() {
-            Ok(MacroTask::ResolvePromise(root_value)) => {
+        match macro_task {
+            MacroTask::ResolvePromise(root_value) => {
                 let value = root_value.take(&mut self.agent);
                 if let Value::Promise(promise) = value {
                     let promise_capability = PromiseCapability::from_promise(promise, false);
@@ -212,7 +381,6 @@ impl Runtime {
                     panic!("Attempted to resolve a non-promise value");
                 }
             }
-            _ => {}
         }
     }
 }
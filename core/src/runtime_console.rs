@@ -0,0 +1,50 @@
+use std::sync::atomic::Ordering;
+
+use crate::HostData;
+
+/// A live snapshot of runtime activity, surfaced by the runtime console so
+/// users can diagnose hung event loops and leaked timers that otherwise keep
+/// [`any_pending_macro_tasks`](crate::RuntimeHostHooks::any_pending_macro_tasks)
+/// true forever.
+#[derive(Debug, Clone, Copy)]
+pub struct RuntimeActivity {
+    /// Number of macro tasks still in flight.
+    pub pending_macro_tasks: u32,
+    /// Number of intervals currently registered.
+    pub active_intervals: usize,
+    /// Number of async tasks currently registered.
+    pub active_tasks: usize,
+}
+
+impl RuntimeActivity {
+    /// Sample the current activity from `host_data`.
+    pub fn sample(host_data: &HostData) -> Self {
+        Self {
+            pending_macro_tasks: host_data.macro_task_count.load(Ordering::Relaxed),
+            active_intervals: host_data.intervals.borrow().len(),
+            active_tasks: host_data.tasks.borrow().len(),
+        }
+    }
+}
+
+/// Install the runtime console subscriber, following the tokio-console /
+/// tracing-subscriber approach: the task spawn/abort events emitted by
+/// [`HostData`], the `handle_macro_task` span and the per-tick activity counts
+/// of the event loop are formatted to stderr. Intervals emit no events of
+/// their own; they appear only as the `active_intervals` count. Called once from
+/// [`Runtime::new`](crate::Runtime) when `RuntimeConfig.console` is set.
+///
+/// Safe to call more than once; a global subscriber is only installed on the
+/// first successful call.
+pub fn install_console_subscriber() {
+    use tracing::level_filters::LevelFilter;
+    use tracing_subscriber::fmt;
+
+    // `try_init` is a no-op (returning `Err`) if a subscriber is already set,
+    // which keeps repeated CLI invocations from panicking.
+    let _ = fmt()
+        .with_max_level(LevelFilter::TRACE)
+        .with_target(true)
+        .with_writer(std::io::stderr)
+        .try_init();
+}
@@ -0,0 +1,217 @@
+use std::{
+    any::Any,
+    cell::RefCell,
+    fs::{File, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+};
+
+use andromeda_core::{Extension, ExtensionOp, HostData, Permissions, Resource, ResourceId};
+use nova_vm::ecmascript::{
+    builtins::ArgumentsList,
+    execution::{agent::ExceptionType, Agent, JsResult},
+    types::Value,
+};
+
+use crate::RuntimeMacroTask;
+
+/// An open file handle held in the [`ResourceTable`](andromeda_core::ResourceTable).
+/// Subsequent read/write/seek ops address it by [`ResourceId`] instead of
+/// re-opening the path each call.
+pub struct FsFile {
+    file: RefCell<File>,
+}
+
+impl Resource for FsFile {
+    fn name(&self) -> &str {
+        "fsFile"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[derive(Default)]
+pub struct FsExt;
+
+impl FsExt {
+    pub fn new_extension() -> Extension {
+        Extension {
+            name: "fs",
+            ops: vec![
+                ExtensionOp::new("internal_read_text_file", Self::read_text_file, 1),
+                ExtensionOp::new("internal_write_text_file", Self::write_text_file, 2),
+                ExtensionOp::new("internal_open", Self::open, 2),
+                ExtensionOp::new("internal_read", Self::read, 2),
+                ExtensionOp::new("internal_write", Self::write, 2),
+                ExtensionOp::new("internal_seek", Self::seek, 2),
+                ExtensionOp::new("internal_close", Self::close, 1),
+            ],
+            storage: None,
+        }
+    }
+
+    /// Read a whole UTF-8 text file by path, gated on the `read` permission.
+    fn read_text_file(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
+        Self::check(agent, Permission::Read)?;
+
+        let path = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|err| agent.throw_exception(ExceptionType::Error, err.to_string()))?;
+        Ok(Value::from_string(agent, contents))
+    }
+
+    /// Write a whole UTF-8 text file by path, gated on the `write` permission.
+    fn write_text_file(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
+        Self::check(agent, Permission::Write)?;
+
+        let path = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let contents = args.get(1).to_string(agent)?.as_str(agent).to_string();
+        std::fs::write(&path, contents)
+            .map_err(|err| agent.throw_exception(ExceptionType::Error, err.to_string()))?;
+        Ok(Value::Undefined)
+    }
+
+    /// Open `path` for reading and/or writing, returning a numeric resource id
+    /// the streaming ops below operate on. `write` mode additionally requires
+    /// the `write` permission.
+    fn open(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
+        let path = args.get(0).to_string(agent)?.as_str(agent).to_string();
+        let writable = args.get(1).to_boolean(agent);
+
+        Self::check(agent, Permission::Read)?;
+        if writable {
+            Self::check(agent, Permission::Write)?;
+        }
+
+        let file = OpenOptions::new()
+            .read(true)
+            .write(writable)
+            .create(writable)
+            .open(&path)
+            .map_err(|err| agent.throw_exception(ExceptionType::Error, err.to_string()))?;
+
+        let id = Self::resource_table(agent, |table| {
+            table.add(FsFile {
+                file: RefCell::new(file),
+            })
+        });
+        Ok(Value::from_f64(agent, id.index() as f64))
+    }
+
+    /// Read up to `len` bytes from an open handle, returning them as a string.
+    fn read(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
+        let id = Self::resource_id(agent, &args)?;
+        let len = args.get(1).to_number(agent)?.into_f64(agent) as usize;
+
+        let bytes = Self::with_file(agent, id, |file| {
+            let mut buf = vec![0u8; len];
+            let read = file.read(&mut buf)?;
+            buf.truncate(read);
+            Ok(buf)
+        })?;
+
+        let contents = String::from_utf8_lossy(&bytes).into_owned();
+        Ok(Value::from_string(agent, contents))
+    }
+
+    /// Write a string to an open handle, returning the number of bytes written.
+    fn write(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
+        let id = Self::resource_id(agent, &args)?;
+        let data = args.get(1).to_string(agent)?.as_str(agent).to_string();
+
+        let written = Self::with_file(agent, id, |file| file.write(data.as_bytes()))?;
+        Ok(Value::from_f64(agent, written as f64))
+    }
+
+    /// Seek an open handle to an absolute byte offset, returning the new offset.
+    fn seek(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
+        let id = Self::resource_id(agent, &args)?;
+        let offset = args.get(1).to_number(agent)?.into_f64(agent) as u64;
+
+        let pos = Self::with_file(agent, id, |file| file.seek(SeekFrom::Start(offset)))?;
+        Ok(Value::from_f64(agent, pos as f64))
+    }
+
+    /// Close an open handle, dropping the underlying file and freeing the slot.
+    fn close(agent: &mut Agent, _this: Value, args: ArgumentsList) -> JsResult<Value> {
+        let id = Self::resource_id(agent, &args)?;
+        let closed = Self::resource_table(agent, |table| table.close(id));
+        if let Some(fut) = closed {
+            futures::executor::block_on(fut);
+        }
+        Ok(Value::Undefined)
+    }
+
+    /// Run `op` against the [`File`] behind `id`, mapping a missing handle or an
+    /// IO error to a JS exception.
+    fn with_file<T>(
+        agent: &mut Agent,
+        id: ResourceId,
+        op: impl FnOnce(&mut File) -> std::io::Result<T>,
+    ) -> JsResult<T> {
+        let resource = Self::resource_table(agent, |table| table.get(id));
+        let resource = match resource {
+            Some(resource) => resource,
+            None => {
+                return Err(agent.throw_exception(
+                    ExceptionType::Error,
+                    format!("Bad resource id: {}", id.index()),
+                ))
+            }
+        };
+        let fs_file = match resource.as_any().downcast_ref::<FsFile>() {
+            Some(fs_file) => fs_file,
+            None => {
+                return Err(agent.throw_exception(
+                    ExceptionType::Error,
+                    format!("Bad resource id: {}", id.index()),
+                ))
+            }
+        };
+        let result = op(&mut fs_file.file.borrow_mut());
+        result.map_err(|err| agent.throw_exception(ExceptionType::Error, err.to_string()))
+    }
+
+    /// Read the [`ResourceId`] argument (first positional) from `args`.
+    fn resource_id(agent: &mut Agent, args: &ArgumentsList) -> JsResult<ResourceId> {
+        let index = args.get(0).to_number(agent)?.into_f64(agent) as u32;
+        Ok(ResourceId::from_index(index))
+    }
+
+    /// Borrow the host [`ResourceTable`](andromeda_core::ResourceTable) and run
+    /// `f` against it.
+    fn resource_table<T>(
+        agent: &mut Agent,
+        f: impl FnOnce(&andromeda_core::ResourceTable) -> T,
+    ) -> T {
+        let host_data = agent.get_host_data();
+        let host_data: &HostData<RuntimeMacroTask> = host_data.downcast_ref().unwrap();
+        f(&host_data.resource_table)
+    }
+
+    /// Consult the permission set before touching the disk, raising a JS error
+    /// on denial.
+    fn check(agent: &mut Agent, permission: Permission) -> JsResult<()> {
+        // Copy the permission set out and drop the host-data borrow before the
+        // mutable `throw_exception` call.
+        let permissions = {
+            let host_data = agent.get_host_data();
+            let host_data: &HostData<RuntimeMacroTask> = host_data.downcast_ref().unwrap();
+            let storage = host_data.storage.borrow();
+            storage.get::<Permissions>().copied().unwrap_or_default()
+        };
+
+        let result = match permission {
+            Permission::Read => permissions.check_read(),
+            Permission::Write => permissions.check_write(),
+        };
+        result.map_err(|denied| agent.throw_exception(ExceptionType::Error, denied.to_string()))
+    }
+}
+
+/// The filesystem capability an op requires.
+enum Permission {
+    Read,
+    Write,
+}
@@ -1,6 +1,6 @@
 use std::time::Instant;
 
-use andromeda_core::{Extension, ExtensionOp, HostData, OpsStorage};
+use andromeda_core::{Extension, ExtensionOp, HostData, OpsStorage, Permissions};
 use nova_vm::ecmascript::{
     builtins::ArgumentsList,
     execution::{Agent, JsResult},
@@ -31,22 +31,32 @@ impl PerformanceExt {
 
     /// Returns the number of milliseconds since the start of the program.
     fn internal_now(agent: &mut Agent, _this: Value, _args: ArgumentsList) -> JsResult<Value> {
-        let host_data = agent.get_host_data();
-        let host_data: &HostData<RuntimeMacroTask> = host_data.downcast_ref().unwrap();
-        let storage = host_data.storage.borrow();
-        let state = storage.get::<PerformanceResource>().unwrap();
-        let start_time = state.start_time;
-        let elapsed = start_time.elapsed();
-        let seconds = elapsed.as_secs();
-        let subsec_nanos = elapsed.subsec_nanos();
-
-        // If the permission is not enabled
-        // Round the nano result on 2 milliseconds
-        // see: https://developer.mozilla.org/en-US/docs/Web/API/DOMHighResTimeStamp#Reduced_time_precision
-        // TODO: Implement a way to enable/disable this behavior
-
-        let _ms = (seconds as f64 * 1000.0) + (subsec_nanos as f64 / 1_000_000.0);
-        
-        Ok(Value::pos_zero())
+        // Read the elapsed time and permission set, then drop the host-data
+        // borrow before handing `agent` to the value constructor.
+        let ms = {
+            let host_data = agent.get_host_data();
+            let host_data: &HostData<RuntimeMacroTask> = host_data.downcast_ref().unwrap();
+            let storage = host_data.storage.borrow();
+            let state = storage.get::<PerformanceResource>().unwrap();
+            let elapsed = state.start_time.elapsed();
+            let seconds = elapsed.as_secs();
+            let mut subsec_nanos = elapsed.subsec_nanos();
+
+            // Unless the `hrtime` permission is granted, round the sub-second
+            // part down onto a 2ms grid to mitigate timing side channels.
+            // see: https://developer.mozilla.org/en-US/docs/Web/API/DOMHighResTimeStamp#Reduced_time_precision
+            let hrtime = storage
+                .get::<Permissions>()
+                .map(|permissions| permissions.hrtime)
+                .unwrap_or(false);
+            if !hrtime {
+                const GRID_NS: u32 = 2_000_000; // 2ms
+                subsec_nanos = (subsec_nanos / GRID_NS) * GRID_NS;
+            }
+
+            (seconds as f64 * 1000.0) + (subsec_nanos as f64 / 1_000_000.0)
+        };
+
+        Ok(Value::from_f64(agent, ms))
     }
 }